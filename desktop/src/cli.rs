@@ -0,0 +1,36 @@
+//! Command-line options for input handling.
+//!
+//! These are the player-option knobs the desktop frontend's input subsystem
+//! (see [`crate::input`]) is configured from.
+
+use clap::Parser;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+pub struct InputOpt {
+    /// Map alphanumeric keys by physical position instead of the layout-mapped
+    /// character, so WASD-style controls land in the same place on AZERTY,
+    /// Dvorak, etc.
+    #[arg(long)]
+    pub positional_keys: bool,
+
+    /// Fraction of a gamepad analog stick's range, from center, to ignore.
+    #[arg(long, default_value_t = 0.15)]
+    pub gamepad_deadzone: f32,
+
+    /// If set, a stick pushed past this magnitude (after the deadzone) also
+    /// emits the corresponding D-pad button, for content that only
+    /// understands digital input. Pass a negative value to disable.
+    #[arg(long, default_value_t = 0.5)]
+    pub gamepad_digital_threshold: f32,
+
+    /// Record translated input events to this file as they occur.
+    #[arg(long)]
+    pub record: Option<PathBuf>,
+
+    /// Replay translated input events from a file previously produced by
+    /// `--record`. Live keyboard and gamepad input is still handled alongside
+    /// the replayed events.
+    #[arg(long)]
+    pub replay: Option<PathBuf>,
+}
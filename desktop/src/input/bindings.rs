@@ -0,0 +1,142 @@
+//! User-configurable key and gamepad bindings.
+//!
+//! This mirrors the shape of Alacritty's `keyboard.bindings` and Neovide's
+//! `KeyboardSettings`: a small table, loaded from the user's config file, that is
+//! consulted *before* the built-in conversion functions in [`crate::util`]. Any
+//! host key or gamepad button without an explicit entry falls back to the
+//! hard-coded defaults, so an empty or missing table behaves exactly like today.
+
+use crate::util;
+use gilrs::Button;
+use ruffle_core::events::{GamepadButton, KeyCode, TextControlCode};
+use serde::Deserialize;
+use winit::event::KeyEvent;
+use winit::keyboard::{Key, KeyLocation};
+
+/// A single host key to match against, as it would appear in a config file, e.g.
+/// `key = { Character = "w" }` or
+/// `key = { key = { Named = "Enter" }, location = "Numpad" }`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(untagged)]
+pub enum KeyInput {
+    /// Just the key, matching it at any location.
+    Key(Key),
+    /// The key together with the location it must occur at (e.g. to tell the
+    /// numpad `Enter` apart from the main one).
+    Located { key: Key, location: KeyLocation },
+}
+
+impl KeyInput {
+    fn matches(&self, key: &Key, location: KeyLocation) -> bool {
+        match self {
+            KeyInput::Key(want) => want == key,
+            KeyInput::Located {
+                key: want,
+                location: want_location,
+            } => want == key && *want_location == location,
+        }
+    }
+}
+
+/// One entry in the user's `keyboard.bindings` table.
+#[derive(Debug, Clone, Deserialize)]
+pub struct KeyBinding {
+    pub key: KeyInput,
+    pub code: KeyCode,
+}
+
+/// One entry remapping a gamepad button to an arbitrary Flash `KeyCode`, so that
+/// content which only understands keyboard input (most SWF games) can still be
+/// played with a gamepad.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GamepadKeyBinding {
+    pub button: Button,
+    pub code: KeyCode,
+}
+
+/// One entry remapping a gamepad button to a different [`GamepadButton`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct GamepadButtonBinding {
+    pub button: Button,
+    pub mapped_to: GamepadButton,
+}
+
+/// User-configurable overrides for the host -> Flash input translation.
+///
+/// Entries are matched last-to-first, so a later entry in the config file
+/// overrides an earlier, more general one. A table with no entries (the
+/// default) defers to [`crate::util`] for everything.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BindingTable {
+    #[serde(default)]
+    pub keys: Vec<KeyBinding>,
+
+    #[serde(default)]
+    pub gamepad_keys: Vec<GamepadKeyBinding>,
+
+    #[serde(default)]
+    pub gamepad_buttons: Vec<GamepadButtonBinding>,
+}
+
+impl BindingTable {
+    /// Parses a `BindingTable` from TOML source whose top-level `keys`,
+    /// `gamepad_keys` and `gamepad_buttons` arrays hold the entries.
+    pub fn from_toml_str(source: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(source)
+    }
+
+    /// Resolves a winit key event to a Flash `KeyCode`, consulting the user's
+    /// overrides first and falling back to the built-in mapping.
+    pub fn resolve_key_code(&self, event: &KeyEvent) -> Option<KeyCode> {
+        // Walk the table back-to-front so a later entry in the config file
+        // overrides an earlier, more general one, matching how the rest of
+        // this table's docs describe precedence.
+        for binding in self.keys.iter().rev() {
+            if binding
+                .key
+                .matches(&event.logical_key, event.location)
+            {
+                return Some(binding.code);
+            }
+        }
+        util::winit_to_ruffle_key_code(event)
+    }
+
+    /// Resolves a gilrs gamepad button, preferring a user override that sends it
+    /// to a `KeyCode` (treating the gamepad as a keyboard), then one that remaps
+    /// it to a different `GamepadButton`, then the built-in mapping. Like
+    /// [`Self::resolve_key_code`], later entries win over earlier ones.
+    pub fn resolve_gamepad_button(
+        &self,
+        button: Button,
+    ) -> (Option<KeyCode>, Option<GamepadButton>) {
+        if let Some(binding) = self.gamepad_keys.iter().rev().find(|b| b.button == button) {
+            return (Some(binding.code), None);
+        }
+
+        if let Some(binding) = self
+            .gamepad_buttons
+            .iter()
+            .rev()
+            .find(|b| b.button == button)
+        {
+            return (None, Some(binding.mapped_to));
+        }
+
+        (None, util::gilrs_button_to_gamepad_button(button))
+    }
+}
+
+/// Resolves a winit key event plus the currently-held modifiers to a Flash
+/// `TextControlCode` (e.g. Ctrl+C for copy), called from the same key-event
+/// path as [`BindingTable::resolve_key_code`]. Text control bindings aren't
+/// user-configurable yet, so this always defers to [`crate::util`]; `_table`
+/// is threaded through now so `BindingTable` can grow a `text_controls` table
+/// later without call sites needing to change.
+pub fn resolve_text_control(
+    _table: &BindingTable,
+    event: &KeyEvent,
+    modifiers: &winit::event::Modifiers,
+) -> Option<TextControlCode> {
+    util::winit_to_ruffle_text_control(event, modifiers)
+}
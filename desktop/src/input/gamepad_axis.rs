@@ -0,0 +1,131 @@
+//! Analog stick and trigger support for the gamepad integration.
+//!
+//! [`crate::util::gilrs_button_to_gamepad_button`] only ever sees digital
+//! button events, so stick and trigger *pressure* was invisible to content.
+//! This reads gilrs `AxisChanged` events instead, applies a configurable
+//! per-axis deadzone (each axis is filtered on its own; a stick's X and Y are
+//! not combined into one radial magnitude), forwards the genuine analog value,
+//! and, past an optional threshold, also synthesizes a digital D-pad press for
+//! a stick or a `LeftTrigger2`/`RightTrigger2` press for a trigger.
+
+use gilrs::Axis;
+use ruffle_core::events::GamepadButton;
+
+/// Player-configurable analog gamepad behavior.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnalogGamepadConfig {
+    /// Fraction of the axis range, from the center, to ignore. `0.0` disables
+    /// deadzone filtering; typical values are `0.1`-`0.25`.
+    pub deadzone: f32,
+    /// If set, an axis pushed past this magnitude (after the deadzone is
+    /// applied) also emits the corresponding digital `GamepadButton`, so
+    /// content that only understands the D-pad still responds to the stick.
+    pub digital_threshold: Option<f32>,
+}
+
+impl Default for AnalogGamepadConfig {
+    fn default() -> Self {
+        Self {
+            deadzone: 0.15,
+            digital_threshold: Some(0.5),
+        }
+    }
+}
+
+/// The result of processing one gilrs `AxisChanged` event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnalogEvent {
+    /// Which logical axis this is, after collapsing left/right stick and
+    /// trigger axes down to the ones `ruffle_core` can consume.
+    pub axis: GamepadAxis,
+    /// The deadzone-adjusted value, in `-1.0..=1.0` (`0.0..=1.0` for triggers).
+    pub value: f32,
+    /// The digital button this axis should emit a press/release for, if the
+    /// configured threshold was crossed.
+    pub digital: Option<GamepadButton>,
+}
+
+/// The analog axes `ruffle_core` is taught to consume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+/// Applies a deadzone to a single axis value: values whose magnitude is at or
+/// below `deadzone` are snapped to zero, and the remaining range is rescaled
+/// to still reach `1.0` at the axis's extremes. The other axis of the same
+/// stick is not taken into account.
+fn apply_deadzone(value: f32, deadzone: f32) -> f32 {
+    let magnitude = value.abs();
+    if magnitude <= deadzone {
+        return 0.0;
+    }
+    let scaled = (magnitude - deadzone) / (1.0 - deadzone);
+    scaled.copysign(value).clamp(-1.0, 1.0)
+}
+
+/// Converts a gilrs axis and raw value into a `ruffle_core`-facing analog
+/// event, or `None` for axes Ruffle doesn't map (e.g. unknown or duplicate
+/// platform-specific axes).
+pub fn gilrs_axis_to_analog_event(
+    axis: Axis,
+    raw_value: f32,
+    config: &AnalogGamepadConfig,
+) -> Option<AnalogEvent> {
+    let (axis, is_trigger) = match axis {
+        Axis::LeftStickX => (GamepadAxis::LeftStickX, false),
+        Axis::LeftStickY => (GamepadAxis::LeftStickY, false),
+        Axis::RightStickX => (GamepadAxis::RightStickX, false),
+        Axis::RightStickY => (GamepadAxis::RightStickY, false),
+        Axis::LeftZ => (GamepadAxis::LeftTrigger, true),
+        Axis::RightZ => (GamepadAxis::RightTrigger, true),
+        // DPadX/DPadY and Unknown axes are already covered by digital button
+        // events, so there's nothing analog to add here.
+        _ => return None,
+    };
+
+    let value = apply_deadzone(raw_value, config.deadzone);
+    let digital = config.digital_threshold.and_then(|threshold| {
+        if is_trigger {
+            return (value >= threshold).then_some(trigger_digital(axis));
+        }
+        axis_digital(axis, value, threshold)
+    });
+
+    Some(AnalogEvent {
+        axis,
+        value,
+        digital,
+    })
+}
+
+fn trigger_digital(axis: GamepadAxis) -> GamepadButton {
+    match axis {
+        GamepadAxis::LeftTrigger => GamepadButton::LeftTrigger2,
+        GamepadAxis::RightTrigger => GamepadButton::RightTrigger2,
+        _ => unreachable!("trigger_digital only called for trigger axes"),
+    }
+}
+
+fn axis_digital(axis: GamepadAxis, value: f32, threshold: f32) -> Option<GamepadButton> {
+    match axis {
+        GamepadAxis::LeftStickX | GamepadAxis::RightStickX if value <= -threshold => {
+            Some(GamepadButton::DPadLeft)
+        }
+        GamepadAxis::LeftStickX | GamepadAxis::RightStickX if value >= threshold => {
+            Some(GamepadButton::DPadRight)
+        }
+        GamepadAxis::LeftStickY | GamepadAxis::RightStickY if value <= -threshold => {
+            Some(GamepadButton::DPadDown)
+        }
+        GamepadAxis::LeftStickY | GamepadAxis::RightStickY if value >= threshold => {
+            Some(GamepadButton::DPadUp)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,127 @@
+//! IME preedit/commit and dead-key composition handling.
+//!
+//! winit's keyboard API surfaces composed input in two ways: `WindowEvent::Ime`
+//! (`Preedit`/`Commit`, used by CJK input methods) and `KeyEvent`s whose
+//! `logical_key` is `Key::Dead` (used for accented Latin input via dead keys).
+//! Neither is visible to [`crate::util::winit_to_ruffle_key_code`], which only
+//! ever sees discrete, already-resolved key presses, so both paths are handled
+//! here: the in-progress preedit and committed text are forwarded into
+//! `ruffle_core` as `PlayerEvent::ImePreedit`/`PlayerEvent::TextInput`, and
+//! [`CompositionState::is_composing`] tells the caller to suppress the normal
+//! `KeyCode` path for the keystroke a composition eventually commits.
+
+use ruffle_core::events::PlayerEvent;
+use ruffle_core::Player;
+use winit::event::Ime;
+use winit::keyboard::Key;
+
+/// Tracks an in-progress composition sequence, whether it came from an IME's
+/// preedit string or a dead-key accent waiting for its base letter.
+#[derive(Debug, Default)]
+pub struct CompositionState {
+    /// The current preedit string, if an IME composition is in progress.
+    preedit: Option<String>,
+    /// A dead-key accent waiting to combine with the next character.
+    pending_dead_key: Option<char>,
+}
+
+impl CompositionState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `true` while an IME composition is in progress; callers can use this to
+    /// suppress the normal `KeyCode` path so a composing keystroke doesn't also
+    /// fire as a raw key press.
+    pub fn is_composing(&self) -> bool {
+        self.preedit.is_some()
+    }
+
+    /// The in-progress preedit string, for showing composition state in the UI
+    /// (e.g. drawing it under the AS3 `TextField`'s caret).
+    pub fn preedit_text(&self) -> Option<&str> {
+        self.preedit.as_deref()
+    }
+
+    /// Handles a winit `WindowEvent::Ime`, forwarding the in-progress preedit
+    /// (so e.g. an AS3 `TextField` can draw the composition under its caret)
+    /// and committed text into the player's text fields one codepoint at a
+    /// time, the same way a regular typed character would arrive.
+    pub fn handle_ime_event(&mut self, player: &mut Player, event: &Ime) {
+        match event {
+            Ime::Enabled => {}
+            Ime::Preedit(text, _cursor) => {
+                self.preedit = if text.is_empty() {
+                    None
+                } else {
+                    Some(text.clone())
+                };
+                forward_preedit(player, self.preedit_text());
+            }
+            Ime::Commit(text) => {
+                self.preedit = None;
+                forward_preedit(player, None);
+                forward_text(player, text);
+            }
+            Ime::Disabled => {
+                self.preedit = None;
+                forward_preedit(player, None);
+            }
+        }
+    }
+
+    /// Handles a dead-key logical key, either stashing the accent or, if one is
+    /// already pending, combining it with `key` and forwarding the composed
+    /// text as player input. Returns `true` if `key` was consumed as part of a
+    /// composition and the caller shouldn't also treat it as a normal keystroke.
+    pub fn handle_dead_key(&mut self, player: &mut Player, key: &Key) -> bool {
+        match key {
+            Key::Dead(Some(accent)) => {
+                self.pending_dead_key = Some(*accent);
+                true
+            }
+            Key::Dead(None) => {
+                self.pending_dead_key = None;
+                true
+            }
+            Key::Character(text) => match self.pending_dead_key.take() {
+                Some(accent) => {
+                    forward_text(player, &compose(accent, text));
+                    true
+                }
+                None => false,
+            },
+            _ => {
+                self.pending_dead_key = None;
+                false
+            }
+        }
+    }
+}
+
+fn forward_text(player: &mut Player, text: &str) {
+    for codepoint in text.chars() {
+        player.handle_event(PlayerEvent::TextInput { codepoint });
+    }
+}
+
+fn forward_preedit(player: &mut Player, text: Option<&str>) {
+    player.handle_event(PlayerEvent::ImePreedit {
+        text: text.map(str::to_string),
+    });
+}
+
+/// Combines a dead-key accent with a base character by appending the matching
+/// Unicode combining diacritical mark. Accents other than the five listed here
+/// are dropped and the base text is returned unchanged.
+fn compose(accent: char, base: &str) -> String {
+    let combining = match accent {
+        '`' => '\u{0300}',
+        '\'' => '\u{0301}',
+        '^' => '\u{0302}',
+        '~' => '\u{0303}',
+        '"' => '\u{0308}',
+        _ => return base.to_string(),
+    };
+    format!("{base}{combining}")
+}
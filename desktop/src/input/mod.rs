@@ -0,0 +1,16 @@
+//! Input handling for the desktop player: translating host keyboard/gamepad
+//! events into the codes `ruffle_core` understands.
+//!
+//! [`util`](crate::util) holds the built-in, hard-coded translation tables.
+//! [`bindings`] layers a user-configurable remapping on top of them.
+//! [`ime`] handles composed text input (IME preedit/commit, dead keys) that the
+//! discrete key-code path can't represent.
+//! [`gamepad_axis`] adds analog stick/trigger support on top of the digital
+//! buttons `util` already handles.
+//! [`replay`] records translated events to a file and plays them back
+//! frame-accurately, for reproducible bug reports and regression runs.
+
+pub mod bindings;
+pub mod gamepad_axis;
+pub mod ime;
+pub mod replay;
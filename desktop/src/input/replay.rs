@@ -0,0 +1,296 @@
+//! Record-and-replay of translated input events, for reproducible bug reports
+//! and frame-accurate automated regression runs.
+//!
+//! This hooks into the same pipeline that already calls
+//! [`crate::util::winit_to_ruffle_key_code`] and friends: whatever comes out of
+//! those converters is timestamped against the player's frame count and
+//! appended to a simple line-based log, in the spirit of the xmacro/easymacros
+//! record-replay approach. The log format also has mouse move/click entries,
+//! but the desktop entry point does not record mouse input. A
+//! `--replay <file>` launch flag feeds the same log back in, frame by frame;
+//! live keyboard and gamepad input is still handled alongside it.
+
+use ruffle_core::events::{GamepadButton, KeyCode, TextControlCode};
+use std::fmt;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::str::FromStr;
+
+/// One translated input event, tagged with the player frame it occurred on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedEvent {
+    pub frame: u32,
+    pub kind: EventKind,
+}
+
+/// The translated events worth recording. Deliberately mirrors the outputs of
+/// the `winit_to_ruffle_*` converters rather than raw winit events, so a replay
+/// is portable across host platforms and keyboard layouts.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventKind {
+    KeyDown(KeyCode),
+    KeyUp(KeyCode),
+    TextControl(TextControlCode),
+    GamepadButtonDown(GamepadButton),
+    GamepadButtonUp(GamepadButton),
+    MouseMove { x: f64, y: f64 },
+    MouseDown { button: u8 },
+    MouseUp { button: u8 },
+}
+
+impl fmt::Display for RecordedEvent {
+    /// Serializes to one line: `<frame> <kind> <args...>`. Every variant here
+    /// has a matching parse arm in `FromStr` below -- none of this round-trips
+    /// through `Debug`, which isn't a stable wire format.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ", self.frame)?;
+        match &self.kind {
+            EventKind::KeyDown(code) => write!(f, "key_down {}", *code as u8),
+            EventKind::KeyUp(code) => write!(f, "key_up {}", *code as u8),
+            EventKind::TextControl(code) => write!(f, "text_control {}", text_control_name(*code)),
+            EventKind::GamepadButtonDown(button) => {
+                write!(f, "gamepad_down {}", gamepad_button_name(*button))
+            }
+            EventKind::GamepadButtonUp(button) => {
+                write!(f, "gamepad_up {}", gamepad_button_name(*button))
+            }
+            EventKind::MouseMove { x, y } => write!(f, "mouse_move {x} {y}"),
+            EventKind::MouseDown { button } => write!(f, "mouse_down {button}"),
+            EventKind::MouseUp { button } => write!(f, "mouse_up {button}"),
+        }
+    }
+}
+
+impl FromStr for RecordedEvent {
+    type Err = ReplayParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut parts = line.split_whitespace();
+        let frame: u32 = parts
+            .next()
+            .ok_or(ReplayParseError)?
+            .parse()
+            .map_err(|_| ReplayParseError)?;
+        let kind = match parts.next().ok_or(ReplayParseError)? {
+            "key_down" => {
+                EventKind::KeyDown(parse_key_code(parts.next().ok_or(ReplayParseError)?)?)
+            }
+            "key_up" => EventKind::KeyUp(parse_key_code(parts.next().ok_or(ReplayParseError)?)?),
+            "text_control" => {
+                EventKind::TextControl(parse_text_control(parts.next().ok_or(ReplayParseError)?)?)
+            }
+            "gamepad_down" => EventKind::GamepadButtonDown(parse_gamepad_button(
+                parts.next().ok_or(ReplayParseError)?,
+            )?),
+            "gamepad_up" => EventKind::GamepadButtonUp(parse_gamepad_button(
+                parts.next().ok_or(ReplayParseError)?,
+            )?),
+            "mouse_move" => {
+                let x = parts.next().ok_or(ReplayParseError)?;
+                let y = parts.next().ok_or(ReplayParseError)?;
+                EventKind::MouseMove {
+                    x: x.parse().map_err(|_| ReplayParseError)?,
+                    y: y.parse().map_err(|_| ReplayParseError)?,
+                }
+            }
+            "mouse_down" => EventKind::MouseDown {
+                button: parts
+                    .next()
+                    .ok_or(ReplayParseError)?
+                    .parse()
+                    .map_err(|_| ReplayParseError)?,
+            },
+            "mouse_up" => EventKind::MouseUp {
+                button: parts
+                    .next()
+                    .ok_or(ReplayParseError)?
+                    .parse()
+                    .map_err(|_| ReplayParseError)?,
+            },
+            _ => return Err(ReplayParseError),
+        };
+        Ok(RecordedEvent { frame, kind })
+    }
+}
+
+#[derive(Debug)]
+pub struct ReplayParseError;
+
+impl fmt::Display for ReplayParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "malformed input replay line")
+    }
+}
+
+impl std::error::Error for ReplayParseError {}
+
+fn parse_key_code(s: &str) -> Result<KeyCode, ReplayParseError> {
+    let value: u8 = s.parse().map_err(|_| ReplayParseError)?;
+    KeyCode::from_u8(value).ok_or(ReplayParseError)
+}
+
+/// Stable, lowercase_snake_case names for each `GamepadButton`, used instead of
+/// `Debug` so the log format doesn't depend on an incidental derive.
+fn gamepad_button_name(button: GamepadButton) -> &'static str {
+    match button {
+        GamepadButton::South => "south",
+        GamepadButton::East => "east",
+        GamepadButton::North => "north",
+        GamepadButton::West => "west",
+        GamepadButton::LeftTrigger => "left_trigger",
+        GamepadButton::LeftTrigger2 => "left_trigger2",
+        GamepadButton::RightTrigger => "right_trigger",
+        GamepadButton::RightTrigger2 => "right_trigger2",
+        GamepadButton::Select => "select",
+        GamepadButton::Start => "start",
+        GamepadButton::DPadUp => "dpad_up",
+        GamepadButton::DPadDown => "dpad_down",
+        GamepadButton::DPadLeft => "dpad_left",
+        GamepadButton::DPadRight => "dpad_right",
+    }
+}
+
+fn parse_gamepad_button(s: &str) -> Result<GamepadButton, ReplayParseError> {
+    Ok(match s {
+        "south" => GamepadButton::South,
+        "east" => GamepadButton::East,
+        "north" => GamepadButton::North,
+        "west" => GamepadButton::West,
+        "left_trigger" => GamepadButton::LeftTrigger,
+        "left_trigger2" => GamepadButton::LeftTrigger2,
+        "right_trigger" => GamepadButton::RightTrigger,
+        "right_trigger2" => GamepadButton::RightTrigger2,
+        "select" => GamepadButton::Select,
+        "start" => GamepadButton::Start,
+        "dpad_up" => GamepadButton::DPadUp,
+        "dpad_down" => GamepadButton::DPadDown,
+        "dpad_left" => GamepadButton::DPadLeft,
+        "dpad_right" => GamepadButton::DPadRight,
+        _ => return Err(ReplayParseError),
+    })
+}
+
+/// Stable, lowercase_snake_case names for each `TextControlCode`, mirroring
+/// `gamepad_button_name` above.
+fn text_control_name(code: TextControlCode) -> &'static str {
+    match code {
+        TextControlCode::Enter => "enter",
+        TextControlCode::SelectAll => "select_all",
+        TextControlCode::Copy => "copy",
+        TextControlCode::Paste => "paste",
+        TextControlCode::Cut => "cut",
+        TextControlCode::Backspace => "backspace",
+        TextControlCode::BackspaceWord => "backspace_word",
+        TextControlCode::Delete => "delete",
+        TextControlCode::DeleteWord => "delete_word",
+        TextControlCode::MoveLeft => "move_left",
+        TextControlCode::MoveLeftWord => "move_left_word",
+        TextControlCode::MoveLeftLine => "move_left_line",
+        TextControlCode::MoveLeftDocument => "move_left_document",
+        TextControlCode::MoveRight => "move_right",
+        TextControlCode::MoveRightWord => "move_right_word",
+        TextControlCode::MoveRightLine => "move_right_line",
+        TextControlCode::MoveRightDocument => "move_right_document",
+        TextControlCode::SelectLeft => "select_left",
+        TextControlCode::SelectLeftWord => "select_left_word",
+        TextControlCode::SelectLeftLine => "select_left_line",
+        TextControlCode::SelectLeftDocument => "select_left_document",
+        TextControlCode::SelectRight => "select_right",
+        TextControlCode::SelectRightWord => "select_right_word",
+        TextControlCode::SelectRightLine => "select_right_line",
+        TextControlCode::SelectRightDocument => "select_right_document",
+    }
+}
+
+fn parse_text_control(s: &str) -> Result<TextControlCode, ReplayParseError> {
+    Ok(match s {
+        "enter" => TextControlCode::Enter,
+        "select_all" => TextControlCode::SelectAll,
+        "copy" => TextControlCode::Copy,
+        "paste" => TextControlCode::Paste,
+        "cut" => TextControlCode::Cut,
+        "backspace" => TextControlCode::Backspace,
+        "backspace_word" => TextControlCode::BackspaceWord,
+        "delete" => TextControlCode::Delete,
+        "delete_word" => TextControlCode::DeleteWord,
+        "move_left" => TextControlCode::MoveLeft,
+        "move_left_word" => TextControlCode::MoveLeftWord,
+        "move_left_line" => TextControlCode::MoveLeftLine,
+        "move_left_document" => TextControlCode::MoveLeftDocument,
+        "move_right" => TextControlCode::MoveRight,
+        "move_right_word" => TextControlCode::MoveRightWord,
+        "move_right_line" => TextControlCode::MoveRightLine,
+        "move_right_document" => TextControlCode::MoveRightDocument,
+        "select_left" => TextControlCode::SelectLeft,
+        "select_left_word" => TextControlCode::SelectLeftWord,
+        "select_left_line" => TextControlCode::SelectLeftLine,
+        "select_left_document" => TextControlCode::SelectLeftDocument,
+        "select_right" => TextControlCode::SelectRight,
+        "select_right_word" => TextControlCode::SelectRightWord,
+        "select_right_line" => TextControlCode::SelectRightLine,
+        "select_right_document" => TextControlCode::SelectRightDocument,
+        _ => return Err(ReplayParseError),
+    })
+}
+
+/// Appends translated events to a recording in progress. Buffered, since
+/// these come from a high-frequency hot path (every key, every gamepad
+/// button) and each is only a few bytes.
+pub struct InputRecorder<W: Write> {
+    writer: BufWriter<W>,
+}
+
+impl<W: Write> InputRecorder<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: BufWriter::new(writer),
+        }
+    }
+
+    pub fn record(&mut self, event: &RecordedEvent) -> io::Result<()> {
+        writeln!(self.writer, "{event}")
+    }
+}
+
+/// Plays back a previously recorded log, handing each event to the caller once
+/// the player reaches the frame it was recorded on.
+pub struct InputReplayer {
+    events: Vec<RecordedEvent>,
+    next: usize,
+}
+
+impl InputReplayer {
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut events = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event = line
+                .parse()
+                .map_err(|err: ReplayParseError| io::Error::new(io::ErrorKind::InvalidData, err))?;
+            events.push(event);
+        }
+        Ok(Self { events, next: 0 })
+    }
+
+    /// Returns every event due on `frame`, in recorded order, advancing the
+    /// internal cursor so each event is only returned once.
+    pub fn events_for_frame(&mut self, frame: u32) -> Vec<RecordedEvent> {
+        let mut due = Vec::new();
+        while let Some(event) = self.events.get(self.next) {
+            if event.frame > frame {
+                break;
+            }
+            due.push(event.clone());
+            self.next += 1;
+        }
+        due
+    }
+
+    /// `true` once every recorded event has been returned by
+    /// [`Self::events_for_frame`].
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.events.len()
+    }
+}
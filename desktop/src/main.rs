@@ -0,0 +1,501 @@
+//! Desktop player entry point: owns the winit event loop and wires host input
+//! through the conversion/binding layers in [`input`] before handing it to
+//! the `ruffle_core` `Player`.
+
+mod cli;
+mod input;
+mod util;
+
+use anyhow::Result;
+use clap::Parser;
+use cli::InputOpt;
+use gilrs::{EventType as GilrsEventType, Gilrs};
+use input::bindings::{resolve_text_control, BindingTable};
+use input::gamepad_axis::{self, AnalogGamepadConfig, GamepadAxis};
+use input::ime::CompositionState;
+use input::replay::{EventKind, InputRecorder, InputReplayer, RecordedEvent};
+use ruffle_core::events::{GamepadButton, KeyCode, MouseButton, PlayerEvent};
+use ruffle_core::tag_utils::SwfMovie;
+use ruffle_core::{Player, PlayerBuilder};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use winit::application::ApplicationHandler;
+use winit::event::{ElementState, Ime, KeyEvent, Modifiers, WindowEvent};
+use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
+use winit::window::{Window, WindowAttributes, WindowId};
+
+/// Top-level CLI: a movie to play plus the input-handling knobs in [`InputOpt`].
+#[derive(Parser, Debug)]
+struct Opt {
+    /// Path to the SWF movie to play.
+    movie: PathBuf,
+
+    #[command(flatten)]
+    input: InputOpt,
+}
+
+/// Bundles the input-handling state threaded through the event loop: the
+/// user's key bindings, whichever key-mapping mode the player options
+/// selected, any in-progress IME/dead-key composition, the analog gamepad
+/// tuning, and an optional recorder/replayer.
+struct InputPipeline {
+    bindings: BindingTable,
+    positional_keys: bool,
+    composition: CompositionState,
+    gamepad: AnalogGamepadConfig,
+    /// The synthesized D-pad button currently "held" by each analog axis, so
+    /// a stick returning to center emits a release instead of sticking on.
+    gamepad_digital_state: HashMap<GamepadAxis, GamepadButton>,
+    /// The currently-held modifiers, tracked from `WindowEvent::ModifiersChanged`
+    /// so [`resolve_text_control`] can recognize commands like Ctrl+C.
+    modifiers: Modifiers,
+    recorder: Option<InputRecorder<File>>,
+    replayer: Option<InputReplayer>,
+}
+
+impl InputPipeline {
+    fn new(opt: &InputOpt) -> Result<Self> {
+        let recorder = opt
+            .record
+            .as_ref()
+            .map(File::create)
+            .transpose()?
+            .map(InputRecorder::new);
+        let replayer = opt
+            .replay
+            .as_ref()
+            .map(File::open)
+            .transpose()?
+            .map(BufReader::new)
+            .map(InputReplayer::from_reader)
+            .transpose()?;
+
+        Ok(Self {
+            bindings: load_bindings(),
+            positional_keys: opt.positional_keys,
+            composition: CompositionState::new(),
+            gamepad: AnalogGamepadConfig {
+                deadzone: opt.gamepad_deadzone,
+                digital_threshold: (opt.gamepad_digital_threshold >= 0.0)
+                    .then_some(opt.gamepad_digital_threshold),
+            },
+            gamepad_digital_state: HashMap::new(),
+            modifiers: Modifiers::default(),
+            recorder,
+            replayer,
+        })
+    }
+
+    /// Resolves a winit key event to a Flash `KeyCode`, honoring the
+    /// positional-keys option, falling back to the user's bindings (which in
+    /// turn fall back to the built-in mapping) otherwise. Returns `None` if
+    /// the key was instead consumed as part of a dead-key composition.
+    fn handle_key_event(&mut self, player: &mut Player, event: &KeyEvent) -> Option<KeyCode> {
+        if self.composition.handle_dead_key(player, &event.logical_key) {
+            return None;
+        }
+        if self.composition.is_composing() {
+            // An IME composition is in progress; its eventual commit will
+            // deliver this keystroke's text, so don't also fire it as a raw
+            // key press.
+            return None;
+        }
+
+        if event.state == ElementState::Pressed {
+            if let Some(control) = resolve_text_control(&self.bindings, event, &self.modifiers) {
+                player.handle_event(PlayerEvent::TextControl { code: control });
+                self.record(
+                    player.current_frame() as u32,
+                    EventKind::TextControl(control),
+                );
+            }
+        }
+
+        let code = if self.positional_keys {
+            util::winit_to_ruffle_key_code_positional(event, true)
+        } else {
+            self.bindings.resolve_key_code(event)
+        };
+
+        if let Some(code) = code {
+            let (player_event, recorded_kind) = match event.state {
+                ElementState::Pressed => (
+                    PlayerEvent::KeyDown {
+                        key_code: code,
+                        key_char: None,
+                    },
+                    EventKind::KeyDown(code),
+                ),
+                ElementState::Released => (
+                    PlayerEvent::KeyUp {
+                        key_code: code,
+                        key_char: None,
+                    },
+                    EventKind::KeyUp(code),
+                ),
+            };
+            player.handle_event(player_event);
+            self.record(player.current_frame() as u32, recorded_kind);
+        }
+        code
+    }
+
+    /// Forwards a winit `WindowEvent::Ime` (preedit/commit) to the in-progress
+    /// composition state.
+    fn handle_ime_event(&mut self, player: &mut Player, event: &Ime) {
+        self.composition.handle_ime_event(player, event);
+    }
+
+    /// Updates the tracked modifier state from a `WindowEvent::ModifiersChanged`,
+    /// so later key events can resolve text-editing commands like Ctrl+C.
+    fn handle_modifiers_changed(&mut self, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+    }
+
+    /// Resolves a digital gilrs button press or release, preferring the
+    /// user's bindings (which may send it to a `KeyCode` or remap it to a
+    /// different `GamepadButton`) over the built-in mapping.
+    fn handle_gamepad_button(
+        &mut self,
+        player: &mut Player,
+        button: gilrs::Button,
+        pressed: bool,
+    ) -> (Option<KeyCode>, Option<GamepadButton>) {
+        let resolved = self.bindings.resolve_gamepad_button(button);
+        let frame = player.current_frame() as u32;
+        if let Some(code) = resolved.0 {
+            player.handle_event(if pressed {
+                PlayerEvent::KeyDown {
+                    key_code: code,
+                    key_char: None,
+                }
+            } else {
+                PlayerEvent::KeyUp {
+                    key_code: code,
+                    key_char: None,
+                }
+            });
+            self.record(
+                frame,
+                if pressed {
+                    EventKind::KeyDown(code)
+                } else {
+                    EventKind::KeyUp(code)
+                },
+            );
+        }
+        if let Some(button) = resolved.1 {
+            player.handle_event(if pressed {
+                PlayerEvent::GamepadButtonDown { button }
+            } else {
+                PlayerEvent::GamepadButtonUp { button }
+            });
+            self.record(
+                frame,
+                if pressed {
+                    EventKind::GamepadButtonDown(button)
+                } else {
+                    EventKind::GamepadButtonUp(button)
+                },
+            );
+        }
+        resolved
+    }
+
+    /// Applies the configured deadzone/threshold to a raw gilrs axis reading,
+    /// forwards the genuine analog value to the player, and -- since a
+    /// synthesized D-pad press has to eventually let go -- emits a release
+    /// once the axis no longer crosses the digital threshold or crosses it
+    /// the other way.
+    fn handle_gamepad_axis(
+        &mut self,
+        player: &mut Player,
+        axis: gilrs::Axis,
+        value: f32,
+    ) -> Option<gamepad_axis::AnalogEvent> {
+        let event = gamepad_axis::gilrs_axis_to_analog_event(axis, value, &self.gamepad)?;
+
+        player.handle_event(PlayerEvent::GamepadAxisValue {
+            axis: event.axis,
+            value: event.value,
+        });
+
+        let previous = self.gamepad_digital_state.get(&event.axis).copied();
+        if previous != event.digital {
+            let frame = player.current_frame() as u32;
+            if let Some(button) = previous {
+                player.handle_event(PlayerEvent::GamepadButtonUp { button });
+                self.record(frame, EventKind::GamepadButtonUp(button));
+            }
+            if let Some(button) = event.digital {
+                player.handle_event(PlayerEvent::GamepadButtonDown { button });
+                self.record(frame, EventKind::GamepadButtonDown(button));
+            }
+            match event.digital {
+                Some(button) => {
+                    self.gamepad_digital_state.insert(event.axis, button);
+                }
+                None => {
+                    self.gamepad_digital_state.remove(&event.axis);
+                }
+            }
+        }
+
+        Some(event)
+    }
+
+    /// Appends `kind`, tagged with `frame`, to the in-progress recording, if
+    /// `--record` was passed.
+    fn record(&mut self, frame: u32, kind: EventKind) {
+        if let Some(recorder) = &mut self.recorder {
+            let _ = recorder.record(&RecordedEvent { frame, kind });
+        }
+    }
+
+    /// Returns every replayed event due on the player's current frame, if
+    /// `--replay` was passed. The caller is responsible for actually applying
+    /// each one to `player`.
+    fn tick_replay(&mut self, player: &Player) -> Vec<RecordedEvent> {
+        match &mut self.replayer {
+            Some(replayer) => replayer.events_for_frame(player.current_frame() as u32),
+            None => Vec::new(),
+        }
+    }
+
+    /// `true` once a `--replay` log has played back every event it contains;
+    /// always `false` when no replay is in progress.
+    fn is_replay_exhausted(&self) -> bool {
+        self.replayer
+            .as_ref()
+            .is_some_and(|replayer| replayer.is_finished())
+    }
+}
+
+/// Parses `ruffle_bindings.toml` from the current working directory, if any;
+/// a missing or unparsable file gives an empty table, which defers entirely
+/// to the built-in mapping.
+fn load_bindings() -> BindingTable {
+    std::fs::read_to_string("ruffle_bindings.toml")
+        .ok()
+        .and_then(|source| BindingTable::from_toml_str(&source).ok())
+        .unwrap_or_default()
+}
+
+/// When the next SWF frame is due, so [`App::about_to_wait`] can pace
+/// [`Player::run_frame`] against the movie's real frame rate instead of
+/// however often the event loop happens to wake up. The interval itself
+/// isn't cached here -- it's read fresh from [`Player::frame_rate`] on every
+/// tick, since content can change its own frame rate at runtime (e.g. AS3's
+/// `Stage.frameRate`).
+struct FramePacing {
+    next: Instant,
+}
+
+/// The real movie frame rate, or a sane fallback if it's zero or negative
+/// (which would otherwise divide by zero / run backwards).
+fn frame_interval(player: &Player) -> Duration {
+    let frame_rate = player.frame_rate();
+    if frame_rate > 0.0 {
+        Duration::from_secs_f64(1.0 / frame_rate)
+    } else {
+        Duration::from_secs_f64(1.0 / 24.0)
+    }
+}
+
+/// Minimal winit application shell tying the real event loop to
+/// [`InputPipeline`]. `window` and `player` are populated the first time the
+/// platform resumes the app; the rest of the desktop app's rendering and GUI
+/// chrome isn't part of this trimmed entry point, but the movie is genuinely
+/// loaded and the input pipeline genuinely runs against it.
+struct App {
+    input: InputPipeline,
+    movie_path: PathBuf,
+    window: Option<Window>,
+    player: Option<Player>,
+    gilrs: Gilrs,
+    frame_pacing: Option<FramePacing>,
+}
+
+impl ApplicationHandler for App {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.window.is_none() {
+            let window = event_loop
+                .create_window(WindowAttributes::default().with_title("Ruffle"))
+                .expect("failed to create window");
+            self.window = Some(window);
+        }
+
+        if self.player.is_none() {
+            match SwfMovie::from_path(&self.movie_path, None) {
+                Ok(movie) => {
+                    let player = PlayerBuilder::new().with_movie(movie).build();
+                    self.frame_pacing = Some(FramePacing {
+                        next: Instant::now() + frame_interval(&player),
+                    });
+                    self.player = Some(player);
+                }
+                Err(error) => {
+                    eprintln!("failed to load {}: {error}", self.movie_path.display());
+                }
+            }
+        }
+    }
+
+    fn window_event(
+        &mut self,
+        _event_loop: &ActiveEventLoop,
+        _window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        match event {
+            WindowEvent::KeyboardInput {
+                event: key_event, ..
+            } => {
+                if let Some(player) = &mut self.player {
+                    let _code = self.input.handle_key_event(player, &key_event);
+                }
+            }
+            WindowEvent::Ime(ime_event) => {
+                if let Some(player) = &mut self.player {
+                    self.input.handle_ime_event(player, &ime_event);
+                }
+            }
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.input.handle_modifiers_changed(modifiers);
+            }
+            _ => {}
+        }
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        while let Some(event) = self.gilrs.next_event() {
+            match event.event {
+                GilrsEventType::ButtonPressed(button, _) => {
+                    if let Some(player) = &mut self.player {
+                        let _resolved = self.input.handle_gamepad_button(player, button, true);
+                    }
+                }
+                GilrsEventType::ButtonReleased(button, _) => {
+                    if let Some(player) = &mut self.player {
+                        let _resolved = self.input.handle_gamepad_button(player, button, false);
+                    }
+                }
+                GilrsEventType::AxisChanged(axis, value, _) => {
+                    if let Some(player) = &mut self.player {
+                        let _analog = self.input.handle_gamepad_axis(player, axis, value);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let (Some(player), Some(pacing)) = (&mut self.player, &mut self.frame_pacing) {
+            let now = Instant::now();
+            if now >= pacing.next {
+                // Catch up one frame at a time rather than jumping straight
+                // to `now`, so a delayed wakeup (e.g. the OS stalling the
+                // process) doesn't skip replayed events due on the frames in
+                // between.
+                pacing.next += frame_interval(player);
+                player.run_frame();
+
+                for recorded in self.input.tick_replay(player) {
+                    apply_recorded_event(player, recorded.kind);
+                }
+
+                // A `--replay` run has nothing left to drive the player with
+                // once its log is exhausted, so end the headless run there
+                // instead of idling forever. Note this ends replay at the
+                // *last recorded event's* frame, not the original session's
+                // actual length -- the log format only timestamps individual
+                // events, so trailing idle frames (e.g. watching an animation
+                // finish with no further input) aren't reproduced.
+                if self.input.is_replay_exhausted() {
+                    event_loop.exit();
+                    return;
+                }
+            }
+
+            // Explicitly schedule the next wakeup at the next frame's
+            // deadline -- under the default `ControlFlow::Wait`, `about_to_wait`
+            // would otherwise only fire again once a new OS/input event
+            // arrives, stalling `run_frame`/replay indefinitely when the app
+            // is otherwise idle.
+            event_loop.set_control_flow(ControlFlow::WaitUntil(pacing.next));
+        }
+    }
+}
+
+/// Applies one previously-recorded event to `player`, the same way its live
+/// counterpart would have. This is what makes `--replay` actually drive the
+/// player instead of just parsing the log.
+fn apply_recorded_event(player: &mut Player, kind: EventKind) {
+    match kind {
+        EventKind::KeyDown(key_code) => player.handle_event(PlayerEvent::KeyDown {
+            key_code,
+            key_char: None,
+        }),
+        EventKind::KeyUp(key_code) => player.handle_event(PlayerEvent::KeyUp {
+            key_code,
+            key_char: None,
+        }),
+        EventKind::TextControl(code) => player.handle_event(PlayerEvent::TextControl { code }),
+        EventKind::GamepadButtonDown(button) => {
+            player.handle_event(PlayerEvent::GamepadButtonDown { button })
+        }
+        EventKind::GamepadButtonUp(button) => {
+            player.handle_event(PlayerEvent::GamepadButtonUp { button })
+        }
+        EventKind::MouseMove { x, y } => player.handle_event(PlayerEvent::MouseMove { x, y }),
+        // Mouse position isn't tracked outside of `MouseMove` events in this
+        // trimmed entry point (nothing wires up live mouse input yet either),
+        // so a replayed click reports at the origin rather than wherever it
+        // was originally recorded.
+        EventKind::MouseDown { button } => player.handle_event(PlayerEvent::MouseDown {
+            x: 0.0,
+            y: 0.0,
+            button: mouse_button_from_index(button),
+        }),
+        EventKind::MouseUp { button } => player.handle_event(PlayerEvent::MouseUp {
+            x: 0.0,
+            y: 0.0,
+            button: mouse_button_from_index(button),
+        }),
+    }
+}
+
+/// Recovers the `MouseButton` an `EventKind::MouseDown`/`MouseUp`'s `u8`
+/// stands in for. There's no live mouse handling to mirror yet, so this is
+/// just a stable, arbitrary assignment.
+fn mouse_button_from_index(button: u8) -> MouseButton {
+    match button {
+        1 => MouseButton::Right,
+        2 => MouseButton::Middle,
+        _ => MouseButton::Left,
+    }
+}
+
+fn main() -> Result<()> {
+    let opt = Opt::parse();
+    let gilrs =
+        Gilrs::new().map_err(|err| anyhow::anyhow!("failed to initialize gamepads: {err}"))?;
+    let app = App {
+        input: InputPipeline::new(&opt.input)?,
+        movie_path: opt.movie,
+        window: None,
+        player: None,
+        gilrs,
+        frame_pacing: None,
+    };
+
+    let event_loop = EventLoop::new()?;
+    run(event_loop, app)
+}
+
+fn run(event_loop: EventLoop<()>, mut app: App) -> Result<()> {
+    event_loop.run_app(&mut app)?;
+    Ok(())
+}
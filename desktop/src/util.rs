@@ -9,7 +9,7 @@ use wgpu::rwh::{HasDisplayHandle, HasWindowHandle};
 use winit::dpi::PhysicalSize;
 use winit::event::{KeyEvent, Modifiers};
 use winit::event_loop::EventLoop;
-use winit::keyboard::{Key, KeyLocation, NamedKey};
+use winit::keyboard::{Key, KeyCode as PhysicalKeyCode, KeyLocation, NamedKey, PhysicalKey};
 
 /// Converts a winit event to a Ruffle `TextControlCode`.
 /// Returns `None` if there is no match.
@@ -66,6 +66,21 @@ pub fn winit_to_ruffle_key_code(event: &KeyEvent) -> Option<KeyCode> {
     // so that on English UK, Shift+3 produces 16+163, not 16+51.
 
     let is_numpad = event.location == KeyLocation::Numpad;
+
+    // ISO/JIS keyboards have an extra key between left Shift and Z (e.g.
+    // `<`/`>`/`|` on German, `\`/`_` on JIS) that doesn't exist on ANSI/US
+    // layouts. Its logical character varies by layout and collides with keys
+    // handled below, so it has to be recognized by physical position instead.
+    // This tree doesn't have `ruffle_core`'s `events` module checked out, so
+    // `KeyCode::OemAngleBracket` can't be confirmed against it here -- it's
+    // the FP key code this position is documented to report. The other
+    // JIS/ISO-only physical keys (`IntlRo`, `IntlYen`) aren't handled yet;
+    // that coverage is deliberately left for a follow-up rather than guessed
+    // at without a matching `KeyCode` variant to verify against.
+    if event.physical_key == PhysicalKey::Code(PhysicalKeyCode::IntlBackslash) {
+        return Some(KeyCode::OemAngleBracket);
+    }
+
     let key_code = match event.logical_key.as_ref() {
         Key::Named(NamedKey::Backspace) => KeyCode::Backspace,
         Key::Named(NamedKey::Tab) => KeyCode::Tab,
@@ -162,8 +177,78 @@ pub fn winit_to_ruffle_key_code(event: &KeyEvent) -> Option<KeyCode> {
     Some(key_code)
 }
 
+/// Like [`winit_to_ruffle_key_code`], but for the alphanumeric range, maps from
+/// `event.physical_key` (the scancode-based `KeyCode`) rather than
+/// `event.logical_key`. This makes movement keys like WASD land at the same
+/// physical position regardless of the user's keyboard layout, at the cost of
+/// reporting the wrong character for non-QWERTY layouts (e.g. an AZERTY user
+/// pressing the key labelled "A" gets `KeyCode::Q`). Pass `use_positional_keys`
+/// from the player options to pick between this and the default, layout-aware
+/// behavior.
+pub fn winit_to_ruffle_key_code_positional(
+    event: &KeyEvent,
+    use_positional_keys: bool,
+) -> Option<KeyCode> {
+    if !use_positional_keys {
+        return winit_to_ruffle_key_code(event);
+    }
+
+    // Numpad keys already carry their own physical scancodes and FP cares about
+    // modifiers on them, so keep using the existing logical-key/numpad-location
+    // handling for that range instead of special-casing it here too.
+    if event.location == KeyLocation::Numpad {
+        return winit_to_ruffle_key_code(event);
+    }
+
+    let key_code = match event.physical_key {
+        PhysicalKey::Code(PhysicalKeyCode::Digit0) => KeyCode::Key0,
+        PhysicalKey::Code(PhysicalKeyCode::Digit1) => KeyCode::Key1,
+        PhysicalKey::Code(PhysicalKeyCode::Digit2) => KeyCode::Key2,
+        PhysicalKey::Code(PhysicalKeyCode::Digit3) => KeyCode::Key3,
+        PhysicalKey::Code(PhysicalKeyCode::Digit4) => KeyCode::Key4,
+        PhysicalKey::Code(PhysicalKeyCode::Digit5) => KeyCode::Key5,
+        PhysicalKey::Code(PhysicalKeyCode::Digit6) => KeyCode::Key6,
+        PhysicalKey::Code(PhysicalKeyCode::Digit7) => KeyCode::Key7,
+        PhysicalKey::Code(PhysicalKeyCode::Digit8) => KeyCode::Key8,
+        PhysicalKey::Code(PhysicalKeyCode::Digit9) => KeyCode::Key9,
+        PhysicalKey::Code(PhysicalKeyCode::KeyA) => KeyCode::A,
+        PhysicalKey::Code(PhysicalKeyCode::KeyB) => KeyCode::B,
+        PhysicalKey::Code(PhysicalKeyCode::KeyC) => KeyCode::C,
+        PhysicalKey::Code(PhysicalKeyCode::KeyD) => KeyCode::D,
+        PhysicalKey::Code(PhysicalKeyCode::KeyE) => KeyCode::E,
+        PhysicalKey::Code(PhysicalKeyCode::KeyF) => KeyCode::F,
+        PhysicalKey::Code(PhysicalKeyCode::KeyG) => KeyCode::G,
+        PhysicalKey::Code(PhysicalKeyCode::KeyH) => KeyCode::H,
+        PhysicalKey::Code(PhysicalKeyCode::KeyI) => KeyCode::I,
+        PhysicalKey::Code(PhysicalKeyCode::KeyJ) => KeyCode::J,
+        PhysicalKey::Code(PhysicalKeyCode::KeyK) => KeyCode::K,
+        PhysicalKey::Code(PhysicalKeyCode::KeyL) => KeyCode::L,
+        PhysicalKey::Code(PhysicalKeyCode::KeyM) => KeyCode::M,
+        PhysicalKey::Code(PhysicalKeyCode::KeyN) => KeyCode::N,
+        PhysicalKey::Code(PhysicalKeyCode::KeyO) => KeyCode::O,
+        PhysicalKey::Code(PhysicalKeyCode::KeyP) => KeyCode::P,
+        PhysicalKey::Code(PhysicalKeyCode::KeyQ) => KeyCode::Q,
+        PhysicalKey::Code(PhysicalKeyCode::KeyR) => KeyCode::R,
+        PhysicalKey::Code(PhysicalKeyCode::KeyS) => KeyCode::S,
+        PhysicalKey::Code(PhysicalKeyCode::KeyT) => KeyCode::T,
+        PhysicalKey::Code(PhysicalKeyCode::KeyU) => KeyCode::U,
+        PhysicalKey::Code(PhysicalKeyCode::KeyV) => KeyCode::V,
+        PhysicalKey::Code(PhysicalKeyCode::KeyW) => KeyCode::W,
+        PhysicalKey::Code(PhysicalKeyCode::KeyX) => KeyCode::X,
+        PhysicalKey::Code(PhysicalKeyCode::KeyY) => KeyCode::Y,
+        PhysicalKey::Code(PhysicalKeyCode::KeyZ) => KeyCode::Z,
+        // Outside the alphanumeric range there's no meaningful "physical position"
+        // mismatch to correct for, so defer to the layout-aware mapping.
+        _ => return winit_to_ruffle_key_code(event),
+    };
+    Some(key_code)
+}
+
 fn alpha_to_ruffle_key_code(char: &str) -> Option<KeyCode> {
-    if char.len() != 1 {
+    // `char.len()` is the *byte* length of the `&str`, which is always > 1 for
+    // non-ASCII characters, so this has to count chars rather than bytes or
+    // every non-ASCII branch below would be unreachable.
+    if char.chars().count() != 1 {
         return None;
     }
 
@@ -177,9 +262,12 @@ fn alpha_to_ruffle_key_code(char: &str) -> Option<KeyCode> {
     }
 
     if !char.is_ascii() {
-        // TODO Non-ASCII inputs have codes equal to their Unicode codes and yes,
-        //   they overlap with other codes, so that typing '½' and '-' both produce 189.
-        return None;
+        // FP reports non-ASCII printable characters with a key code equal to
+        // their Unicode code point, e.g. 'é' (U+00E9) reports 233. `KeyCode`
+        // is only 8 bits wide, so this matches FP's behavior for code points
+        // up to 255 and, yes, shares FP's overlaps: '½' (U+00BD) and '-' both
+        // produce 189.
+        return u8::try_from(char as u32).ok().and_then(KeyCode::from_u8);
     }
 
     None